@@ -172,13 +172,31 @@ impl DynamicArray {
             .into_iter()
             .map(|path| ByteArray::from_utf8(path.to_string()))
             .collect();
-        let dyn_array = DynamicArray {
-            data: data.as_ptr(),
+        // Mimics Vec::into_raw_parts, same as ByteArray::from_vec: transfer
+        // ownership of the backing allocation to the caller instead of
+        // dropping it (and leaving `data` dangling) at the end of scope.
+        let mut data = std::mem::ManuallyDrop::new(data);
+        DynamicArray {
+            data: data.as_mut_ptr(),
             size: data.len(),
             cap: data.capacity(),
             disable_free: false,
-        };
-        dyn_array
+        }
+    }
+}
+
+/// Frees a `DynamicArray` previously returned by `table_file_uris`, `table_files`, or `table_vacuum`.
+#[no_mangle]
+pub extern "C" fn dynamic_array_free(array: *mut DynamicArray) {
+    if array.is_null() {
+        return;
+    }
+
+    unsafe {
+        let array = Box::from_raw(array);
+        if !array.disable_free && !array.data.is_null() {
+            let _ = Vec::from_raw_parts(array.data as *mut ByteArray, array.size, array.cap);
+        }
     }
 }
 #[repr(C)]
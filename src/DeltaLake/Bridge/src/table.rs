@@ -1,8 +1,19 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
-use chrono::Duration;
-use deltalake::{operations::vacuum::VacuumBuilder, DeltaTableBuilder};
+use chrono::{Duration, TimeZone};
+use datafusion::prelude::SessionContext;
+use deltalake::{
+    arrow::{
+        ffi::{FFI_ArrowArray, FFI_ArrowSchema},
+        record_batch::RecordBatch,
+    },
+    operations::{
+        merge::MergeBuilder, restore::RestoreBuilder, vacuum::VacuumBuilder, write::WriteBuilder,
+    },
+    DeltaTableBuilder, SaveMode,
+};
 use libc::c_void;
+use tokio::sync::{OwnedRwLockReadGuard, OwnedRwLockWriteGuard, RwLock};
 
 use crate::{
     error::{DeltaTableError, DeltaTableErrorCode},
@@ -10,8 +21,9 @@ use crate::{
     ByteArray, ByteArrayRef, DynamicArray, Map, SerializedBuffer,
 };
 
+/// Guards the inner `DeltaTable` against overlapping FFI calls on the same handle.
 pub struct RawDeltaTable {
-    table: deltalake::DeltaTable,
+    table: Arc<RwLock<deltalake::DeltaTable>>,
 }
 
 #[repr(C)]
@@ -59,7 +71,7 @@ type GenericErrorCallback =
 #[no_mangle]
 pub extern "C" fn table_uri(table: *const RawDeltaTable) -> *mut ByteArray {
     let table = unsafe { &*table };
-    let uri = table.table.table_uri();
+    let uri = table.table.blocking_read().table_uri();
     ByteArray::from_utf8(uri).into_raw()
 }
 
@@ -147,7 +159,7 @@ pub extern "C" fn table_file_uris(
     runtime: *mut Runtime,
     table: *mut RawDeltaTable,
 ) -> GenericOrError {
-    do_with_table_and_runtime_sync(runtime, table, |rt, tbl| match tbl.table.get_file_uris() {
+    do_with_table_and_runtime_sync(runtime, table, |rt, tbl| match tbl.get_file_uris() {
         Ok(file_uris) => unsafe {
             GenericOrError {
                 bytes: Box::into_raw(Box::new(DynamicArray::from_vec_string(file_uris.collect())))
@@ -166,7 +178,7 @@ pub extern "C" fn table_file_uris(
 
 #[no_mangle]
 pub extern "C" fn table_files(runtime: *mut Runtime, table: *mut RawDeltaTable) -> GenericOrError {
-    do_with_table_and_runtime_sync(runtime, table, |rt, tbl| match tbl.table.get_files_iter() {
+    do_with_table_and_runtime_sync(runtime, table, |rt, tbl| match tbl.get_files_iter() {
         Ok(paths) => unsafe {
             GenericOrError {
                 bytes: Box::into_raw(Box::new(DynamicArray::from_vec_string(
@@ -184,6 +196,57 @@ pub extern "C" fn table_files(runtime: *mut Runtime, table: *mut RawDeltaTable)
     })
 }
 
+/// One entry of `DeltaTable::history`.
+#[derive(serde::Serialize)]
+struct HistoryEntry {
+    version: Option<i64>,
+    timestamp: Option<i64>,
+    operation: Option<String>,
+    operation_parameters: HashMap<String, String>,
+    operation_metrics: HashMap<String, String>,
+    user_metadata: Option<String>,
+}
+
+/// Unwraps a bare JSON string instead of re-quoting it; other JSON values fall back to their JSON form.
+fn json_value_to_string(value: serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+/// Serializes `history` as a JSON array of `HistoryEntry` objects, handed over in a
+/// `SerializedBuffer` with offset 0. The payload is JSON, not the flatbuffer format of `table_schema`.
+fn serialize_history(commits: &[deltalake::kernel::CommitInfo]) -> Vec<u8> {
+    let entries: Vec<HistoryEntry> = commits
+        .iter()
+        .map(|commit| HistoryEntry {
+            version: commit.version,
+            timestamp: commit.timestamp,
+            operation: commit.operation.clone(),
+            operation_parameters: commit
+                .operation_parameters
+                .clone()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(k, v)| (k, json_value_to_string(v)))
+                .collect(),
+            operation_metrics: commit
+                .operation_metrics
+                .clone()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(k, v)| (k, json_value_to_string(v)))
+                .collect(),
+            user_metadata: commit.user_metadata.clone(),
+        })
+        .collect();
+
+    // unwrap is safe because HistoryEntry only contains primitives and maps
+    // of strings, none of which can fail to serialize.
+    serde_json::to_vec(&entries).unwrap()
+}
+
 #[no_mangle]
 pub extern "C" fn history(
     runtime: *mut Runtime,
@@ -191,7 +254,26 @@ pub extern "C" fn history(
     limit: usize,
     callback: GenericErrorCallback,
 ) {
-    unimplemented!()
+    let limit = if limit > 0 { Some(limit) } else { None };
+    do_with_table_and_runtime_read(runtime, table, move |rt, tbl| async move {
+        match tbl.history(limit).await {
+            Ok(commits) => {
+                let buffer = serialize_history(&commits);
+                let fb = SerializedBuffer {
+                    data: buffer.as_ptr(),
+                    size: buffer.len(),
+                    offset: 0,
+                };
+                unsafe {
+                    callback(std::ptr::addr_of!(fb) as *const c_void, std::ptr::null());
+                }
+            }
+            Err(err) => unsafe {
+                let error = DeltaTableError::from_error(rt, err);
+                callback(std::ptr::null(), Box::into_raw(Box::new(error)));
+            },
+        };
+    });
 }
 
 #[no_mangle]
@@ -200,8 +282,8 @@ pub extern "C" fn table_update_incremental(
     table: *mut RawDeltaTable,
     callback: TableEmptyCallback,
 ) {
-    do_with_table_and_runtime(runtime, table, move |rt, tbl| async move {
-        match tbl.table.update_incremental(None).await {
+    do_with_table_and_runtime(runtime, table, move |rt, mut tbl| async move {
+        match tbl.update_incremental(None).await {
             Ok(_) => unsafe {
                 callback(std::ptr::null());
             },
@@ -220,8 +302,8 @@ pub extern "C" fn table_load_version(
     version: i64,
     callback: TableEmptyCallback,
 ) {
-    do_with_table_and_runtime(runtime, table, move |rt, tbl| async move {
-        match tbl.table.load_version(version).await {
+    do_with_table_and_runtime(runtime, table, move |rt, mut tbl| async move {
+        match tbl.load_version(version).await {
             Ok(_) => unsafe { callback(std::ptr::null()) },
             Err(err) => {
                 let error = DeltaTableError::from_error(rt, err);
@@ -238,17 +320,469 @@ pub extern "C" fn table_load_with_datetime(
     ts_milliseconds: i64,
     callback: TableEmptyCallback,
 ) {
-    unimplemented!()
+    let datetime = match chrono::Utc.timestamp_millis_opt(ts_milliseconds) {
+        chrono::LocalResult::Single(datetime) => datetime,
+        _ => {
+            let rt = unsafe { &mut *runtime };
+            let error = DeltaTableError::new(
+                rt,
+                DeltaTableErrorCode::Protocol,
+                &format!("invalid timestamp: {ts_milliseconds}"),
+            );
+            unsafe { callback(Box::into_raw(Box::new(error))) };
+            return;
+        }
+    };
+
+    do_with_table_and_runtime(runtime, table, move |rt, mut tbl| async move {
+        match tbl.load_with_datetime(datetime).await {
+            Ok(_) => unsafe { callback(std::ptr::null()) },
+            Err(err) => {
+                let error = DeltaTableError::from_error(rt, err);
+                unsafe { callback(Box::into_raw(Box::new(error))) }
+            }
+        };
+    })
+}
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub enum MergeClauseKind {
+    MatchedUpdate = 0,
+    MatchedDelete = 1,
+    NotMatchedInsert = 2,
+    NotMatchedBySourceUpdate = 3,
+    NotMatchedBySourceDelete = 4,
+}
+
+/// One `WHEN [NOT] MATCHED [BY SOURCE] ...` arm of the merge. `predicate`/`assignments` may be null.
+#[repr(C)]
+pub struct MergeClause {
+    kind: MergeClauseKind,
+    predicate: *const ByteArrayRef,
+    assignments: *const Map,
+}
+
+#[repr(C)]
+pub struct MergeOptions {
+    /// The join predicate between target and source, e.g. "target.id = source.id".
+    predicate: *const ByteArrayRef,
+    clauses: *const MergeClause,
+    clauses_count: libc::size_t,
+}
+
+#[derive(serde::Serialize)]
+struct MergeMetrics {
+    num_target_rows_inserted: i64,
+    num_target_rows_updated: i64,
+    num_target_rows_deleted: i64,
+    num_target_files_added: i64,
+    num_target_files_removed: i64,
 }
 
 #[no_mangle]
 pub extern "C" fn table_merge(
     runtime: *mut Runtime,
     table: *mut RawDeltaTable,
-    version: i64,
+    source_array: *mut FFI_ArrowArray,
+    source_schema: *mut FFI_ArrowSchema,
+    options: *const MergeOptions,
+    callback: GenericErrorCallback,
+) {
+    let rt = unsafe { &mut *runtime };
+
+    let source = match unsafe { import_record_batch(source_array, source_schema) } {
+        Ok(batch) => batch,
+        Err(err) => unsafe {
+            callback(
+                std::ptr::null(),
+                Box::into_raw(Box::new(DeltaTableError::new(
+                    rt,
+                    DeltaTableErrorCode::Protocol,
+                    &err.to_string(),
+                ))),
+            );
+            return;
+        }
+    };
+
+    let (predicate, clauses) = unsafe { parse_merge_options(options) };
+
+    do_with_table_and_runtime(runtime, table, move |rt, mut tbl| async move {
+        match merge(&mut tbl, source, predicate, clauses).await {
+            Ok(metrics) => {
+                let buffer = serde_json::to_vec(&metrics).unwrap();
+                let fb = SerializedBuffer {
+                    data: buffer.as_ptr(),
+                    size: buffer.len(),
+                    offset: 0,
+                };
+                unsafe {
+                    callback(std::ptr::addr_of!(fb) as *const c_void, std::ptr::null());
+                }
+            }
+            Err(err) => unsafe {
+                let error = DeltaTableError::from_error(rt, err);
+                callback(std::ptr::null(), Box::into_raw(Box::new(error)))
+            },
+        };
+    });
+}
+
+/// Imports a single record batch handed over the Arrow C Data Interface. Takes ownership of `array`/`schema`.
+unsafe fn import_record_batch(
+    array: *mut FFI_ArrowArray,
+    schema: *mut FFI_ArrowSchema,
+) -> Result<RecordBatch, deltalake::arrow::error::ArrowError> {
+    let owned_array = std::ptr::replace(array, FFI_ArrowArray::empty());
+    let owned_schema = std::ptr::replace(schema, FFI_ArrowSchema::empty());
+
+    let array_data = deltalake::arrow::ffi::from_ffi(owned_array, &owned_schema)?;
+    let struct_array = deltalake::arrow::array::make_array(array_data);
+    let struct_array = struct_array
+        .as_any()
+        .downcast_ref::<deltalake::arrow::array::StructArray>()
+        .ok_or_else(|| {
+            deltalake::arrow::error::ArrowError::InvalidArgumentError(
+                "source array must be a struct array representing a record batch".to_string(),
+            )
+        })?;
+    Ok(RecordBatch::from(struct_array))
+}
+
+unsafe fn parse_merge_options(
+    options: *const MergeOptions,
+) -> (String, Vec<(MergeClauseKind, Option<String>, Option<HashMap<String, String>>)>) {
+    let options = &*options;
+    let predicate = (&*options.predicate).to_string();
+
+    let clause_slice: &[MergeClause] = if options.clauses.is_null() {
+        &[]
+    } else {
+        std::slice::from_raw_parts(options.clauses, options.clauses_count)
+    };
+
+    let clauses = clause_slice
+        .iter()
+        .map(|clause| {
+            let predicate = if clause.predicate.is_null() {
+                None
+            } else {
+                Some((&*clause.predicate).to_string())
+            };
+            let assignments = if clause.assignments.is_null() {
+                None
+            } else {
+                Some((&*clause.assignments).data.clone())
+            };
+            (clause.kind, predicate, assignments)
+        })
+        .collect();
+
+    (predicate, clauses)
+}
+
+async fn merge(
+    table: &mut deltalake::DeltaTable,
+    source: RecordBatch,
+    predicate: String,
+    clauses: Vec<(MergeClauseKind, Option<String>, Option<HashMap<String, String>>)>,
+) -> Result<MergeMetrics, deltalake::DeltaTableError> {
+    if table.state.is_none() {
+        return Err(deltalake::DeltaTableError::NoMetadata);
+    }
+
+    // `MergeBuilder` runs the merge as a join through DataFusion, so it takes
+    // the source as a `DataFrame` rather than a bare `RecordBatch`.
+    let source = SessionContext::new().read_batch(source)?;
+
+    let mut builder = MergeBuilder::new(
+        table.log_store(),
+        table.state.clone().unwrap(),
+        predicate,
+        source,
+    )?;
+
+    for (kind, predicate, assignments) in clauses {
+        builder = match kind {
+            MergeClauseKind::MatchedUpdate => builder.when_matched_update(|mut update| {
+                if let Some(predicate) = predicate {
+                    update = update.predicate(predicate);
+                }
+                for (column, expression) in assignments.unwrap_or_default() {
+                    update = update.update(column, expression);
+                }
+                update
+            })?,
+            MergeClauseKind::MatchedDelete => builder.when_matched_delete(|mut delete| {
+                if let Some(predicate) = predicate {
+                    delete = delete.predicate(predicate);
+                }
+                delete
+            })?,
+            MergeClauseKind::NotMatchedInsert => builder.when_not_matched_insert(|mut insert| {
+                if let Some(predicate) = predicate {
+                    insert = insert.predicate(predicate);
+                }
+                for (column, expression) in assignments.unwrap_or_default() {
+                    insert = insert.set(column, expression);
+                }
+                insert
+            })?,
+            MergeClauseKind::NotMatchedBySourceUpdate => {
+                builder.when_not_matched_by_source_update(|mut update| {
+                    if let Some(predicate) = predicate {
+                        update = update.predicate(predicate);
+                    }
+                    for (column, expression) in assignments.unwrap_or_default() {
+                        update = update.update(column, expression);
+                    }
+                    update
+                })?
+            }
+            MergeClauseKind::NotMatchedBySourceDelete => {
+                builder.when_not_matched_by_source_delete(|mut delete| {
+                    if let Some(predicate) = predicate {
+                        delete = delete.predicate(predicate);
+                    }
+                    delete
+                })?
+            }
+        };
+    }
+
+    let (result, metrics) = builder.await?;
+    table.state = result.state;
+
+    Ok(MergeMetrics {
+        num_target_rows_inserted: metrics.num_target_rows_inserted as i64,
+        num_target_rows_updated: metrics.num_target_rows_updated as i64,
+        num_target_rows_deleted: metrics.num_target_rows_deleted as i64,
+        num_target_files_added: metrics.num_target_files_added as i64,
+        num_target_files_removed: metrics.num_target_files_removed as i64,
+    })
+}
+
+#[repr(C)]
+pub enum WriteSaveMode {
+    Append = 0,
+    Overwrite = 1,
+    ErrorIfExists = 2,
+    Ignore = 3,
+}
+
+impl From<&WriteSaveMode> for SaveMode {
+    fn from(mode: &WriteSaveMode) -> Self {
+        match mode {
+            WriteSaveMode::Append => SaveMode::Append,
+            WriteSaveMode::Overwrite => SaveMode::Overwrite,
+            WriteSaveMode::ErrorIfExists => SaveMode::ErrorIfExists,
+            WriteSaveMode::Ignore => SaveMode::Ignore,
+        }
+    }
+}
+
+#[repr(C)]
+pub struct WriteOptions {
+    save_mode: WriteSaveMode,
+    /// Newline-delimited partition column names; null/empty means unpartitioned.
+    partition_by: *const ByteArrayRef,
+    /// Optional "replace where" predicate, used with `Overwrite`.
+    predicate: *const ByteArrayRef,
+    custom_metadata: *const Map,
+}
+
+unsafe fn parse_write_options(
+    options: *const WriteOptions,
+) -> (SaveMode, Vec<String>, Option<String>, Option<HashMap<String, String>>) {
+    let options = &*options;
+    let save_mode = SaveMode::from(&options.save_mode);
+
+    let partition_by = if options.partition_by.is_null() {
+        Vec::new()
+    } else {
+        (&*options.partition_by)
+            .to_str()
+            .split('\n')
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+
+    let predicate = if options.predicate.is_null() {
+        None
+    } else {
+        (&*options.predicate).to_option_string()
+    };
+
+    let custom_metadata = if options.custom_metadata.is_null() {
+        None
+    } else {
+        Some((&*options.custom_metadata).data.clone())
+    };
+
+    (save_mode, partition_by, predicate, custom_metadata)
+}
+
+/// Writes a single record batch into the table. For multiple batches, prefer `table_writer_*` instead.
+#[no_mangle]
+pub extern "C" fn table_write(
+    runtime: *mut Runtime,
+    table: *mut RawDeltaTable,
+    source_array: *mut FFI_ArrowArray,
+    source_schema: *mut FFI_ArrowSchema,
+    options: *const WriteOptions,
     callback: TableEmptyCallback,
 ) {
-    unimplemented!()
+    let rt = unsafe { &mut *runtime };
+
+    let batch = match unsafe { import_record_batch(source_array, source_schema) } {
+        Ok(batch) => batch,
+        Err(err) => unsafe {
+            callback(Box::into_raw(Box::new(DeltaTableError::new(
+                rt,
+                DeltaTableErrorCode::Protocol,
+                &err.to_string(),
+            ))));
+            return;
+        }
+    };
+
+    let (save_mode, partition_by, predicate, custom_metadata) =
+        unsafe { parse_write_options(options) };
+
+    do_with_table_and_runtime(runtime, table, move |rt, mut tbl| async move {
+        match write(
+            &mut tbl,
+            vec![batch],
+            save_mode,
+            partition_by,
+            predicate,
+            custom_metadata,
+        )
+        .await
+        {
+            Ok(_) => unsafe { callback(std::ptr::null()) },
+            Err(err) => {
+                let error = DeltaTableError::from_error(rt, err);
+                unsafe { callback(Box::into_raw(Box::new(error))) }
+            }
+        };
+    });
+}
+
+async fn write(
+    table: &mut deltalake::DeltaTable,
+    batches: Vec<RecordBatch>,
+    save_mode: SaveMode,
+    partition_by: Vec<String>,
+    predicate: Option<String>,
+    custom_metadata: Option<HashMap<String, String>>,
+) -> Result<(), deltalake::DeltaTableError> {
+    let mut builder = WriteBuilder::new(table.log_store(), table.state.clone())
+        .with_input_batches(batches.into_iter())
+        .with_save_mode(save_mode);
+
+    if !partition_by.is_empty() {
+        builder = builder.with_partition_columns(partition_by);
+    }
+
+    if let Some(predicate) = predicate {
+        builder = builder.with_replace_where(predicate);
+    }
+
+    if let Some(metadata) = custom_metadata {
+        let json_metadata: serde_json::Map<String, serde_json::Value> =
+            metadata.into_iter().map(|(k, v)| (k, v.into())).collect();
+        builder = builder.with_metadata(json_metadata);
+    }
+
+    let result = builder.await?;
+    table.state = result.state;
+    Ok(())
+}
+
+pub struct RawDeltaTableWriter {
+    batches: Vec<RecordBatch>,
+    save_mode: SaveMode,
+    partition_by: Vec<String>,
+    predicate: Option<String>,
+    custom_metadata: Option<HashMap<String, String>>,
+}
+
+/// Creates a streaming writer that accumulates batches until `table_writer_flush` is called.
+#[no_mangle]
+pub extern "C" fn table_writer_new(options: *const WriteOptions) -> *mut RawDeltaTableWriter {
+    let (save_mode, partition_by, predicate, custom_metadata) =
+        unsafe { parse_write_options(options) };
+    Box::into_raw(Box::new(RawDeltaTableWriter {
+        batches: Vec::new(),
+        save_mode,
+        partition_by,
+        predicate,
+        custom_metadata,
+    }))
+}
+
+/// Appends one record batch to the writer's pending buffer. Does not touch the table or the log store.
+#[no_mangle]
+pub extern "C" fn table_writer_write(
+    runtime: *mut Runtime,
+    writer: *mut RawDeltaTableWriter,
+    source_array: *mut FFI_ArrowArray,
+    source_schema: *mut FFI_ArrowSchema,
+) -> *mut DeltaTableError {
+    let rt = unsafe { &mut *runtime };
+    let writer = unsafe { &mut *writer };
+
+    match unsafe { import_record_batch(source_array, source_schema) } {
+        Ok(batch) => {
+            writer.batches.push(batch);
+            std::ptr::null_mut()
+        }
+        Err(err) => Box::into_raw(Box::new(DeltaTableError::new(
+            rt,
+            DeltaTableErrorCode::Protocol,
+            &err.to_string(),
+        ))),
+    }
+}
+
+/// Commits the writer's pending batches. Consumes and frees `writer`; do not also call `table_writer_free`.
+#[no_mangle]
+pub extern "C" fn table_writer_flush(
+    runtime: *mut Runtime,
+    table: *mut RawDeltaTable,
+    writer: *mut RawDeltaTableWriter,
+    callback: TableEmptyCallback,
+) {
+    let writer = unsafe { Box::from_raw(writer) };
+    do_with_table_and_runtime(runtime, table, move |rt, mut tbl| async move {
+        match write(
+            &mut tbl,
+            writer.batches,
+            writer.save_mode,
+            writer.partition_by,
+            writer.predicate,
+            writer.custom_metadata,
+        )
+        .await
+        {
+            Ok(_) => unsafe { callback(std::ptr::null()) },
+            Err(err) => {
+                let error = DeltaTableError::from_error(rt, err);
+                unsafe { callback(Box::into_raw(Box::new(error))) }
+            }
+        };
+    });
+}
+
+/// Frees a writer that was never flushed. Do not call on a writer already passed to `table_writer_flush`.
+#[no_mangle]
+pub extern "C" fn table_writer_free(writer: *mut RawDeltaTableWriter) {
+    unsafe {
+        let _ = Box::from_raw(writer);
+    }
 }
 
 #[no_mangle]
@@ -261,14 +795,138 @@ pub extern "C" fn table_protocol(
     unimplemented!()
 }
 
+#[repr(C)]
+pub struct RestoreOptions {
+    /// Only read when `has_version` is set; lets a version of `0` be
+    /// distinguished from "not provided".
+    version: i64,
+    has_version: bool,
+    /// Only read when `has_timestamp` is set. Mutually exclusive with
+    /// `version`/`has_version`; callers should set exactly one of the two.
+    ts_milliseconds: i64,
+    has_timestamp: bool,
+    ignore_missing_files: bool,
+    protocol_downgrade_allowed: bool,
+    custom_metadata: *const Map,
+}
+
+#[derive(serde::Serialize)]
+struct RestoreMetrics {
+    num_restored_files: i64,
+    num_removed_files: i64,
+}
+
 #[no_mangle]
 pub extern "C" fn table_restore(
     runtime: *mut Runtime,
     table: *mut RawDeltaTable,
-    version: i64,
-    callback: TableEmptyCallback,
+    options: *const RestoreOptions,
+    callback: GenericErrorCallback,
 ) {
-    unimplemented!()
+    let rt = unsafe { &mut *runtime };
+
+    let (version, datetime, ignore_missing_files, protocol_downgrade_allowed, custom_metadata) = unsafe {
+        let options = &*options;
+
+        let datetime = if options.has_timestamp {
+            match chrono::Utc.timestamp_millis_opt(options.ts_milliseconds) {
+                chrono::LocalResult::Single(datetime) => Some(datetime),
+                _ => {
+                    let error = DeltaTableError::new(
+                        rt,
+                        DeltaTableErrorCode::Protocol,
+                        &format!("invalid timestamp: {}", options.ts_milliseconds),
+                    );
+                    callback(std::ptr::null(), Box::into_raw(Box::new(error)));
+                    return;
+                }
+            }
+        } else {
+            None
+        };
+
+        let custom_metadata = if options.custom_metadata.is_null() {
+            None
+        } else {
+            Some((&*options.custom_metadata).data.clone())
+        };
+
+        (
+            options.has_version.then_some(options.version),
+            datetime,
+            options.ignore_missing_files,
+            options.protocol_downgrade_allowed,
+            custom_metadata,
+        )
+    };
+
+    do_with_table_and_runtime(runtime, table, move |rt, mut tbl| async move {
+        match restore(
+            &mut tbl,
+            version,
+            datetime,
+            ignore_missing_files,
+            protocol_downgrade_allowed,
+            custom_metadata,
+        )
+        .await
+        {
+            Ok(metrics) => {
+                let buffer = serde_json::to_vec(&metrics).unwrap();
+                let fb = SerializedBuffer {
+                    data: buffer.as_ptr(),
+                    size: buffer.len(),
+                    offset: 0,
+                };
+                unsafe {
+                    callback(std::ptr::addr_of!(fb) as *const c_void, std::ptr::null());
+                }
+            }
+            Err(err) => unsafe {
+                let error = DeltaTableError::from_error(rt, err);
+                callback(std::ptr::null(), Box::into_raw(Box::new(error)))
+            },
+        };
+    });
+}
+
+async fn restore(
+    table: &mut deltalake::DeltaTable,
+    version: Option<i64>,
+    datetime: Option<chrono::DateTime<chrono::Utc>>,
+    ignore_missing_files: bool,
+    protocol_downgrade_allowed: bool,
+    custom_metadata: Option<HashMap<String, String>>,
+) -> Result<RestoreMetrics, deltalake::DeltaTableError> {
+    if table.state.is_none() {
+        return Err(deltalake::DeltaTableError::NoMetadata);
+    }
+
+    let mut cmd = RestoreBuilder::new(table.log_store(), table.state.clone().unwrap())
+        .with_ignore_missing_files(ignore_missing_files)
+        .with_protocol_downgrade_allowed(protocol_downgrade_allowed);
+
+    if let Some(version) = version {
+        cmd = cmd.with_version_to_restore(version);
+    }
+
+    if let Some(datetime) = datetime {
+        cmd = cmd.with_datetime_to_restore(datetime);
+    }
+
+    if let Some(metadata) = custom_metadata {
+        let json_metadata: serde_json::Map<String, serde_json::Value> =
+            metadata.into_iter().map(|(k, v)| (k, v.into())).collect();
+        cmd = cmd.with_metadata(json_metadata);
+    }
+
+    let (result, metrics) = cmd.await?;
+    table.state = result.state;
+
+    Ok(RestoreMetrics {
+        num_restored_files: metrics.num_restored_file as i64,
+        num_removed_files: metrics.num_removed_file as i64,
+    })
 }
 
 #[no_mangle]
@@ -291,7 +949,7 @@ pub extern "C" fn table_schema(
     do_with_table_and_runtime_sync(
         runtime,
         table,
-        move |rt, tbl| match crate::schema::get_schema(rt, &tbl.table) {
+        move |rt, tbl| match crate::schema::get_schema(rt, &tbl) {
             Ok(schema) => {
                 let (array, offset) = crate::schema::serialize_schema(rt, &schema);
                 let fb = SerializedBuffer {
@@ -316,8 +974,8 @@ pub extern "C" fn table_checkpoint(
     table: *mut RawDeltaTable,
     callback: TableEmptyCallback,
 ) {
-    do_with_table_and_runtime(runtime, table, move |rt, tbl| async move {
-        match deltalake::checkpoints::create_checkpoint(&tbl.table).await {
+    do_with_table_and_runtime_read(runtime, table, move |rt, tbl| async move {
+        match deltalake::checkpoints::create_checkpoint(&tbl).await {
             Ok(_) => unsafe {
                 callback(std::ptr::null());
             },
@@ -357,9 +1015,9 @@ pub extern "C" fn table_vacuum(
             custom_metadata,
         )
     };
-    do_with_table_and_runtime(runtime, table, move |rt, tbl| async move {
+    do_with_table_and_runtime(runtime, table, move |rt, mut tbl| async move {
         match vacuum(
-            &mut tbl.table,
+            &mut tbl,
             dry_run,
             retention_hours,
             enforce_retention_duration,
@@ -413,53 +1071,78 @@ async fn vacuum(
 
 #[no_mangle]
 pub extern "C" fn table_version(table_handle: *mut RawDeltaTable) -> i64 {
-    do_with_table(table_handle, |table| table.table.version())
+    do_with_table(table_handle, |table| table.version())
 }
 
 #[no_mangle]
 pub extern "C" fn table_metadata(table_handle: *mut RawDeltaTable, callback: TableEmptyCallback) {
-    do_with_table(table_handle, |table| match table.table.metadata() {
+    do_with_table(table_handle, |table| match table.metadata() {
         Ok(_) => todo!(),
         Err(_) => todo!(),
     })
 }
 
+/// Spawns `work` onto the runtime with the table's write lock held for the duration.
 fn do_with_table_and_runtime<'a, F, Fut>(rt: *mut Runtime, table: *mut RawDeltaTable, work: F)
 where
-    F: FnOnce(&'a mut Runtime, &'a mut RawDeltaTable) -> Fut + Send + 'static,
+    F: FnOnce(&'a mut Runtime, OwnedRwLockWriteGuard<deltalake::DeltaTable>) -> Fut + Send + 'static,
+    Fut: std::future::Future<Output = ()> + Send,
+{
+    let runtime = unsafe { &mut *rt };
+    let table = unsafe { &*table };
+    let lock = table.table.clone();
+    let runtime_handle = runtime.handle();
+    runtime_handle.spawn(async move {
+        let guard = lock.write_owned().await;
+        work(runtime, guard).await;
+    });
+}
+
+/// Like `do_with_table_and_runtime`, but takes the read lock instead.
+fn do_with_table_and_runtime_read<'a, F, Fut>(rt: *mut Runtime, table: *mut RawDeltaTable, work: F)
+where
+    F: FnOnce(&'a mut Runtime, OwnedRwLockReadGuard<deltalake::DeltaTable>) -> Fut + Send + 'static,
     Fut: std::future::Future<Output = ()> + Send,
 {
     let runtime = unsafe { &mut *rt };
-    let table = unsafe { &mut *table };
+    let table = unsafe { &*table };
+    let lock = table.table.clone();
     let runtime_handle = runtime.handle();
     runtime_handle.spawn(async move {
-        work(runtime, table).await;
+        let guard = lock.read_owned().await;
+        work(runtime, guard).await;
     });
 }
 
+/// Like `do_with_table_and_runtime`, but synchronous and read-only.
 fn do_with_table_and_runtime_sync<'a, F, T>(
     rt: *mut Runtime,
     table: *mut RawDeltaTable,
     work: F,
 ) -> T
 where
-    F: FnOnce(&'a mut Runtime, &'a mut RawDeltaTable) -> T,
+    F: FnOnce(&'a mut Runtime, tokio::sync::RwLockReadGuard<'a, deltalake::DeltaTable>) -> T,
 {
     let runtime = unsafe { &mut *rt };
-    let table = unsafe { &mut *table };
-    work(runtime, table)
+    let table = unsafe { &*table };
+    let guard = table.table.blocking_read();
+    work(runtime, guard)
 }
 
+/// Like `do_with_table_and_runtime_sync`, but without a `Runtime`.
 fn do_with_table<'a, F, T>(table: *mut RawDeltaTable, work: F) -> T
 where
-    F: FnOnce(&'a mut RawDeltaTable) -> T,
+    F: FnOnce(tokio::sync::RwLockReadGuard<'a, deltalake::DeltaTable>) -> T,
 {
-    let table = unsafe { &mut *table };
-    work(table)
+    let table = unsafe { &*table };
+    let guard = table.table.blocking_read();
+    work(guard)
 }
 
 impl RawDeltaTable {
     fn new(table: deltalake::DeltaTable) -> Self {
-        RawDeltaTable { table }
+        RawDeltaTable {
+            table: Arc::new(RwLock::new(table)),
+        }
     }
 }
\ No newline at end of file